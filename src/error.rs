@@ -3,9 +3,9 @@ use std::fmt;
 
 ///
 /// Defines various errors
-/// 
+///
 #[derive(Clone, Debug)]
-pub enum KPcaError {
+pub enum PcaError {
     /// Indicates a failure encountered during Kernel PCA computation
     ComputationFailure(String),
     /// Indicates an invalid Kernel PCA configuration
@@ -14,43 +14,43 @@ pub enum KPcaError {
     InvalidData(String)
 }
 
-impl KPcaError {
+impl PcaError {
 
     ///
     /// Constructs a new ComputationFailure instance
-    /// 
+    ///
     /// # Arguments
-    /// 
+    ///
     /// * `message` - The error message
-    /// 
-    pub fn computation_failure(message: impl Into<String>) -> KPcaError {
-        KPcaError::ComputationFailure(message.into())
+    ///
+    pub fn computation_failure(message: impl Into<String>) -> PcaError {
+        PcaError::ComputationFailure(message.into())
     }
 
     ///
     /// Constructs a new InvalidConfig instance
-    /// 
+    ///
     /// # Arguments
-    /// 
+    ///
     /// * `message` - The error message
-    /// 
-    pub fn invalid_config(message: impl Into<String>) -> KPcaError {
-        KPcaError::InvalidConfig(message.into())
+    ///
+    pub fn invalid_config(message: impl Into<String>) -> PcaError {
+        PcaError::InvalidConfig(message.into())
     }
 
     ///
     /// Constructs a new InvalidData instance
-    /// 
+    ///
     /// # Arguments
-    /// 
+    ///
     /// * `message` - The error message
-    /// 
-    pub fn invalid_data(message: impl Into<String>) -> KPcaError {
-        KPcaError::InvalidData(message.into())
+    ///
+    pub fn invalid_data(message: impl Into<String>) -> PcaError {
+        PcaError::InvalidData(message.into())
     }
 }
 
-impl fmt::Display for KPcaError {
+impl fmt::Display for PcaError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let message = match self {
             Self::ComputationFailure(message) => message,
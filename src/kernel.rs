@@ -1,70 +1,242 @@
 use num::Float;
+use crate::PcaError;
+
+///
+/// A validated, strictly positive hyperparameter value. Can only be constructed via
+/// `Positive::new`, which enforces the invariant, so a `Kernel` variant holding one can
+/// never be built with a non-positive value via struct-literal syntax
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Positive<T>(T);
+
+impl <T: Float> Positive<T> {
+
+    fn new(value: T, name: &str) -> Result<Positive<T>, PcaError> {
+        if value > T::zero() {
+            Ok(Positive(value))
+        } else {
+            Err(PcaError::invalid_config(format!("{} must be positive", name)))
+        }
+    }
+
+    /// Returns the validated value
+    pub fn get(self) -> T {
+        self.0
+    }
+}
+
+///
+/// A validated, non-negative hyperparameter value. Can only be constructed via
+/// `NonNegative::new`, which enforces the invariant, so a `Kernel` variant holding one can
+/// never be built with a negative value via struct-literal syntax
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NonNegative<T>(T);
+
+impl <T: Float> NonNegative<T> {
+
+    fn new(value: T, name: &str) -> Result<NonNegative<T>, PcaError> {
+        if value >= T::zero() {
+            Ok(NonNegative(value))
+        } else {
+            Err(PcaError::invalid_config(format!("{} must be non-negative", name)))
+        }
+    }
+
+    /// Returns the validated value
+    pub fn get(self) -> T {
+        self.0
+    }
+}
+
+///
+/// A validated, strictly positive polynomial degree. Can only be constructed via
+/// `PositiveDegree::new`, which enforces the invariant, so a `Kernel` variant holding one can
+/// never be built with a non-positive degree via struct-literal syntax
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PositiveDegree(i32);
+
+impl PositiveDegree {
+
+    fn new(value: i32) -> Result<PositiveDegree, PcaError> {
+        if value >= 1 {
+            Ok(PositiveDegree(value))
+        } else {
+            Err(PcaError::invalid_config("degree must be positive"))
+        }
+    }
+
+    /// Returns the validated value
+    pub fn get(self) -> i32 {
+        self.0
+    }
+}
 
 ///
 /// Defines various kernel functions
-/// 
+///
 #[derive(Clone, Debug)]
 pub enum Kernel<T: Float> {
-    /// Linear kernel of the form x * x' (Note that this is equivalent to standard PCA)
+    /// Linear kernel of the form x * x' (Note that this is equivalent to standard PCA; fitting
+    /// against this kernel skips the n x n kernel matrix entirely and operates directly on the
+    /// centered training data)
     Linear,
     /// Rational Quadratic kernel of the form (1 + gamma * (x - x')^2)^(-alpha)
-    RationalQuadratic { gamma: T, alpha: T },
+    RationalQuadratic { gamma: Positive<T>, alpha: Positive<T> },
     /// Squared Exponential (or RBF) kernel of the form exp(-gamma * (x - x')^2)
-    SquaredExponential { gamma: T }
+    SquaredExponential { gamma: Positive<T> },
+    /// Laplacian (or Matern-1/2) kernel of the form exp(-gamma * r), where r = ||x - x'||
+    Laplacian { gamma: Positive<T> },
+    /// Matern-3/2 kernel of the form (1 + sqrt(3) * r / l) * exp(-sqrt(3) * r / l), where r = ||x - x'||
+    Matern32 { length_scale: Positive<T> },
+    /// Matern-5/2 kernel of the form (1 + sqrt(5) * r / l + 5 * r^2 / (3 * l^2)) * exp(-sqrt(5) * r / l), where r = ||x - x'||
+    Matern52 { length_scale: Positive<T> },
+    /// Polynomial kernel of the form (gamma * <x, x'> + coef0)^degree. `coef0` must be
+    /// non-negative: the eigendecomposition machinery this kernel feeds into assumes the
+    /// centered kernel matrix is positive semi-definite, which a negative coef0 can violate
+    Polynomial { gamma: Positive<T>, coef0: NonNegative<T>, degree: PositiveDegree },
+    /// Periodic kernel of the form exp(-2 * gamma * sin^2(pi * r / period)), where r = ||x - x'||
+    Periodic { gamma: Positive<T>, period: Positive<T> },
+    /// A placeholder for a Gram matrix supplied directly by the caller, bypassing kernel
+    /// evaluation entirely. Used with `KernelPca::apply_precomputed` for similarities that
+    /// don't come from feature vectors (graphs, strings, custom distances, etc)
+    Precomputed
 }
 
 impl <T: Float> Kernel<T> {
 
     ///
     /// Construct a new Linear kernel
-    /// 
+    ///
     pub fn linear() -> Kernel<T> {
         Kernel::Linear
     }
 
     ///
     /// Construct a new Rational Quadratic kernel
-    /// 
+    ///
     /// # Arguments
-    /// 
-    /// * `gamma` - The gamma scale value
-    /// * `alpha` - The alpha exponent value
-    /// 
-    pub fn rational_quadratic(gamma: T, alpha: T) -> Kernel<T> {
-        Kernel::RationalQuadratic { gamma, alpha }
+    ///
+    /// * `gamma` - The gamma scale value (must be positive)
+    /// * `alpha` - The alpha exponent value (must be positive)
+    ///
+    pub fn rational_quadratic(gamma: T, alpha: T) -> Result<Kernel<T>, PcaError> {
+        let gamma = Positive::new(gamma, "gamma")?;
+        let alpha = Positive::new(alpha, "alpha")?;
+        Ok(Kernel::RationalQuadratic { gamma, alpha })
     }
 
     ///
     /// Construct a new Squared Exponential kernel
-    /// 
+    ///
     /// # Arguments
-    /// 
-    /// * `gamma` - The gamma scale value
-    /// 
-    pub fn squared_exponential(gamma: T) -> Kernel<T> {
-        Kernel::SquaredExponential { gamma }
+    ///
+    /// * `gamma` - The gamma scale value (must be positive)
+    ///
+    pub fn squared_exponential(gamma: T) -> Result<Kernel<T>, PcaError> {
+        let gamma = Positive::new(gamma, "gamma")?;
+        Ok(Kernel::SquaredExponential { gamma })
+    }
+
+    ///
+    /// Construct a new Laplacian kernel
+    ///
+    /// # Arguments
+    ///
+    /// * `gamma` - The gamma scale value (must be positive)
+    ///
+    pub fn laplacian(gamma: T) -> Result<Kernel<T>, PcaError> {
+        let gamma = Positive::new(gamma, "gamma")?;
+        Ok(Kernel::Laplacian { gamma })
+    }
+
+    ///
+    /// Construct a new Matern-3/2 kernel
+    ///
+    /// # Arguments
+    ///
+    /// * `length_scale` - The length-scale value (must be positive)
+    ///
+    pub fn matern_3_2(length_scale: T) -> Result<Kernel<T>, PcaError> {
+        let length_scale = Positive::new(length_scale, "length_scale")?;
+        Ok(Kernel::Matern32 { length_scale })
+    }
+
+    ///
+    /// Construct a new Matern-5/2 kernel
+    ///
+    /// # Arguments
+    ///
+    /// * `length_scale` - The length-scale value (must be positive)
+    ///
+    pub fn matern_5_2(length_scale: T) -> Result<Kernel<T>, PcaError> {
+        let length_scale = Positive::new(length_scale, "length_scale")?;
+        Ok(Kernel::Matern52 { length_scale })
+    }
+
+    ///
+    /// Construct a new Polynomial kernel
+    ///
+    /// # Arguments
+    ///
+    /// * `gamma` - The gamma scale value (must be positive)
+    /// * `coef0` - The additive constant term (must be non-negative, to keep the kernel Mercer)
+    /// * `degree` - The polynomial degree (must be positive)
+    ///
+    pub fn polynomial(gamma: T, coef0: T, degree: i32) -> Result<Kernel<T>, PcaError> {
+        let gamma = Positive::new(gamma, "gamma")?;
+        let coef0 = NonNegative::new(coef0, "coef0")?;
+        let degree = PositiveDegree::new(degree)?;
+        Ok(Kernel::Polynomial { gamma, coef0, degree })
+    }
+
+    ///
+    /// Construct a new Periodic kernel
+    ///
+    /// # Arguments
+    ///
+    /// * `gamma` - The gamma scale value (must be positive)
+    /// * `period` - The period of the kernel (must be positive)
+    ///
+    pub fn periodic(gamma: T, period: T) -> Result<Kernel<T>, PcaError> {
+        let gamma = Positive::new(gamma, "gamma")?;
+        let period = Positive::new(period, "period")?;
+        Ok(Kernel::Periodic { gamma, period })
+    }
+
+    ///
+    /// Construct a new Precomputed kernel placeholder, for use with `KernelPca::apply_precomputed`
+    ///
+    pub fn precomputed() -> Kernel<T> {
+        Kernel::Precomputed
     }
 
     ///
     /// Computes the kernel function for the provided points
-    /// 
+    ///
     /// # Arguments
-    /// 
+    ///
     /// * `a` - The first point
     /// * `b` - The second point
-    /// 
+    ///
     pub fn compute(&self, a: &[T], b: &[T]) -> T {
         match self {
             Self::Linear => compute_linear(a, b),
-            Self::RationalQuadratic { gamma, alpha } => compute_rational_quadratic(a, b, *gamma, *alpha),
-            Self::SquaredExponential { gamma } => compute_squared_exponential(a, b, *gamma)
+            Self::RationalQuadratic { gamma, alpha } => compute_rational_quadratic(a, b, gamma.get(), alpha.get()),
+            Self::SquaredExponential { gamma } => compute_squared_exponential(a, b, gamma.get()),
+            Self::Laplacian { gamma } => compute_laplacian(a, b, gamma.get()),
+            Self::Matern32 { length_scale } => compute_matern_3_2(a, b, length_scale.get()),
+            Self::Matern52 { length_scale } => compute_matern_5_2(a, b, length_scale.get()),
+            Self::Polynomial { gamma, coef0, degree } => compute_polynomial(a, b, gamma.get(), coef0.get(), degree.get()),
+            Self::Periodic { gamma, period } => compute_periodic(a, b, gamma.get(), period.get()),
+            Self::Precomputed => unreachable!("Precomputed kernel has no pointwise form; use KernelPca::apply_precomputed")
         }
     }
 }
 
 // Specialized linear computation
-// Note that this should never actually be used internally by this library
-// Instead, we should use vanilla PCA and avoid constructing the kernel matrix
+// This is what makes the Linear kernel equivalent to standard PCA performed in its dual form
 fn compute_linear<T: Float>(a: &[T], b: &[T]) -> T {
     a
     .iter()
@@ -95,3 +267,53 @@ fn compute_squared_exponential<T: Float>(a: &[T], b: &[T], gamma: T) -> T {
     });
     return (-gamma * ssd).exp()
 }
+
+// Specialized laplacian computation
+fn compute_laplacian<T: Float>(a: &[T], b: &[T], gamma: T) -> T {
+    (-gamma * euclidean_distance(a, b)).exp()
+}
+
+// Specialized Matern-3/2 computation
+fn compute_matern_3_2<T: Float>(a: &[T], b: &[T], length_scale: T) -> T {
+    let r = euclidean_distance(a, b);
+    let scaled = T::from(3.0).unwrap().sqrt() * r / length_scale;
+    (T::one() + scaled) * (-scaled).exp()
+}
+
+// Specialized Matern-5/2 computation
+fn compute_matern_5_2<T: Float>(a: &[T], b: &[T], length_scale: T) -> T {
+    let r = euclidean_distance(a, b);
+    let scaled = T::from(5.0).unwrap().sqrt() * r / length_scale;
+    let quadratic_term = T::from(5.0).unwrap() * r * r / (T::from(3.0).unwrap() * length_scale * length_scale);
+    (T::one() + scaled + quadratic_term) * (-scaled).exp()
+}
+
+// Specialized polynomial computation
+fn compute_polynomial<T: Float>(a: &[T], b: &[T], gamma: T, coef0: T, degree: i32) -> T {
+    let dot = a
+    .iter()
+    .zip(b.iter())
+    .fold(T::zero(), |sum, (&a, &b)| sum + a * b);
+    (gamma * dot + coef0).powi(degree)
+}
+
+// Specialized periodic computation
+fn compute_periodic<T: Float>(a: &[T], b: &[T], gamma: T, period: T) -> T {
+    let r = euclidean_distance(a, b);
+    let s = (T::from(std::f64::consts::PI).unwrap() * r / period).sin();
+    (-T::from(2.0).unwrap() * gamma * s * s).exp()
+}
+
+// Euclidean distance between two points, used by the stationary kernels that are
+// parameterized in terms of distance rather than squared distance
+fn euclidean_distance<T: Float>(a: &[T], b: &[T]) -> T {
+    let ssd = a
+    .iter()
+    .zip(b.iter())
+    .fold(T::zero(), |sum, (&a, &b)| {
+        let diff = a - b;
+        sum + diff * diff
+    });
+    // Guard against a tiny negative ssd from floating point error so r = 0 stays clean
+    ssd.max(T::zero()).sqrt()
+}
@@ -1,8 +1,10 @@
 pub mod kernel;
 mod error;
 
-use nalgebra::{DMatrix, RowDVector, ComplexField, RealField, Scalar, Field};
+use nalgebra::{DMatrix, DVector, RowDVector, ComplexField, RealField, Scalar, Field};
 use num::Float;
+use rand::thread_rng;
+use rand_distr::{Distribution, StandardNormal};
 
 pub use kernel::Kernel;
 pub use error::PcaError;
@@ -10,68 +12,201 @@ pub use error::PcaError;
 
 ///
 /// Define a Kernel PCA configuration
-/// 
+///
 #[derive(Clone, Debug)]
 pub struct KernelPca<T: Float> {
     /// The kernel function
     pub kernel: Kernel<T>,
     /// The embedding dimension
-    pub embed_dim: usize
+    pub embed_dim: usize,
+    /// The SVD solver used to extract the top `embed_dim` components
+    pub solver: SvdSolver
+}
+
+///
+/// Selects the algorithm used to extract the top `embed_dim` singular
+/// components of the centered kernel matrix
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SvdSolver {
+    /// Computes the full dense SVD of the centered kernel matrix
+    Exact,
+    /// Approximates the top components via randomized range finding, which is much
+    /// cheaper than the exact solver when `embed_dim` is small relative to the number
+    /// of training points
+    Randomized {
+        /// Extra random directions sampled beyond `embed_dim` to improve approximation accuracy
+        oversampling: usize,
+        /// Number of power iterations used to sharpen slowly-decaying spectra
+        power_iters: usize
+    }
+}
+
+///
+/// Represents a Kernel PCA model that has been fit to training data. Holds
+/// everything needed to project new, out-of-sample points into the learned
+/// embedding space via the Nystrom formula
+///
+#[derive(Clone, Debug)]
+pub struct FittedKernelPca<T: Float> {
+    // The kernel function the model was fit with
+    kernel: Kernel<T>,
+    // The training data the model was fit against
+    x_train: Vec<Vec<T>>,
+    // Eigenvectors of the centered training kernel matrix, scaled by 1 / sqrt(eigenvalue)
+    alpha: DMatrix<T>,
+    // Per-row means of the (uncentered) training kernel matrix
+    row_means: Vec<T>,
+    // Grand mean of the (uncentered) training kernel matrix
+    grand_mean: T,
+    // Sign-flip correction applied to each embedding dimension
+    signs: Vec<T>,
+    // Retained eigenvalues of the centered training kernel matrix, descending
+    eigenvalues: Vec<T>,
+    // Retained eigenvalues divided by the sum of all eigenvalues of the centered kernel matrix
+    explained_variance_ratio: Vec<T>
 }
 
 impl <T: Float + ComplexField + RealField> KernelPca<T> {
 
     ///
     /// Constructs a new KernelPCA instance
-    /// 
+    ///
     /// # Arguments
-    /// 
+    ///
     /// * `kernel` - The kernel function
     /// * `embed_dim` - The desired embedding dimension
-    /// 
+    ///
     pub fn new(kernel: Kernel<T>, embed_dim: usize) -> KernelPca<T> {
-        KernelPca { kernel, embed_dim }
+        KernelPca { kernel, embed_dim, solver: SvdSolver::Exact }
+    }
+
+    ///
+    /// Configures this instance to approximate the top `embed_dim` components via
+    /// randomized range finding instead of computing the full dense SVD. This trades
+    /// some accuracy for speed on large training sets where `embed_dim` is small
+    /// relative to the number of points
+    ///
+    /// # Arguments
+    ///
+    /// * `oversampling` - Extra random directions sampled beyond `embed_dim` (10 is a reasonable default)
+    /// * `power_iters` - Number of power iterations used to sharpen slowly-decaying spectra
+    ///
+    pub fn with_randomized_svd(mut self, oversampling: usize, power_iters: usize) -> KernelPca<T> {
+        self.solver = SvdSolver::Randomized { oversampling, power_iters };
+        self
     }
 
     ///
     /// Applies Kernel PCA to the provided input data, outputting
-    /// the projected embeddings
-    /// 
+    /// the projected embeddings. Equivalent to fitting against `data`
+    /// and transforming the same data
+    ///
     /// # Arguments
-    /// 
+    ///
     /// * `data` - The input data, as a vector of feature vectors
-    /// 
+    ///
     pub fn apply(&self, data: &Vec<Vec<T>>) -> Result<Vec<Vec<T>>, PcaError> {
+        self.fit(data)?.transform(data)
+    }
+
+    ///
+    /// Fits Kernel PCA against the provided training data, returning a model
+    /// that can project out-of-sample points into the learned embedding space
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The training data, as a vector of feature vectors
+    ///
+    pub fn fit(&self, data: &Vec<Vec<T>>) -> Result<FittedKernelPca<T>, PcaError> {
         self.validate(data)?;
-        // For the linear kernel, we just use vanilla PCA and avoid the kernel matrix
-        let x = match self.kernel {
-            Kernel::Linear => center_data(data)?,
-            _ => center_kernel_matrix(&self.form_kernel_matrix(data))?
-        };
-        let svd = x.svd(true, false);
-        let sv_selection = svd.singular_values.rows(0, self.embed_dim);
-        // Remember we don't need to take the square root for the linear case
-        let sigma = match self.kernel {
-            Kernel::Linear => DMatrix::from_diagonal(&sv_selection),
-            _ => DMatrix::from_diagonal(&sv_selection.map(|v| Float::sqrt(v)))
-        };
-        let u = svd
-        .u
-        .ok_or(PcaError::computation_failure("SVD Failure"))?;
-        let signs = determine_signs(&u, self.embed_dim);
-        let u_selection = u.columns(0, self.embed_dim);
-        let embeddings = u_selection * sigma;
-        return Ok(
-            embeddings
-            .row_iter()
-            .map(|row| {
-                row.iter()
-                .enumerate()
-                .map(|(j, &val)| val * signs[j])
-                .collect()
-            })
-            .collect()
-        )
+        match self.kernel {
+            // For the linear kernel, we just use vanilla PCA and avoid the kernel matrix
+            Kernel::Linear => self.fit_linear(data),
+            _ => self.fit_kernel_matrix(data)
+        }
+    }
+
+    // Fast path for the Linear kernel: operates directly on the n x d centered data matrix
+    // rather than materializing the n x n kernel matrix. This relies on the duality between
+    // the two: the centered linear kernel matrix is K = Xc * Xc^T, so its eigenvectors are
+    // exactly the left singular vectors of Xc and its eigenvalues are the squared singular
+    // values of Xc
+    fn fit_linear(&self, data: &Vec<Vec<T>>) -> Result<FittedKernelPca<T>, PcaError> {
+        let (row_means, grand_mean) = linear_kernel_means(data)?;
+        let xc = center_data(data)?;
+        let svd = xc.svd(true, false);
+        let u = svd.u.ok_or(PcaError::computation_failure("SVD Failure"))?;
+        let singular_values = svd.singular_values.rows(0, self.embed_dim).clone_owned();
+        let u_selection = u.columns(0, self.embed_dim).clone_owned();
+        let signs = determine_signs(&u_selection);
+        // alpha scaled so that sqrt(lambda) * alpha reproduces the u_selection * sigma embeddings;
+        // since lambda = singular_value^2 here, sqrt(lambda) is just the singular value itself
+        let alpha = DMatrix::from_fn(u_selection.nrows(), self.embed_dim, |i, j| {
+            u_selection[(i, j)] / singular_values[j]
+        });
+        let eigenvalues: Vec<T> = singular_values.iter().map(|&s| s * s).collect();
+        // The trace of the centered kernel matrix is the squared Frobenius norm of Xc,
+        // i.e. the sum of the squares of *all* of Xc's singular values, retained or not
+        let total_eigenvalue = svd.singular_values.iter().fold(T::zero(), |sum, &s| sum + s * s);
+        let explained_variance_ratio = compute_explained_variance_ratio(&eigenvalues, total_eigenvalue)?;
+        Ok(FittedKernelPca {
+            kernel: self.kernel.clone(),
+            x_train: data.clone(),
+            alpha,
+            row_means,
+            grand_mean,
+            signs,
+            eigenvalues,
+            explained_variance_ratio
+        })
+    }
+
+    // General path for non-linear kernels: materializes the n x n kernel matrix and runs
+    // the (possibly randomized) SVD of its centered form
+    fn fit_kernel_matrix(&self, data: &Vec<Vec<T>>) -> Result<FittedKernelPca<T>, PcaError> {
+        let k = self.form_kernel_matrix(data);
+        let (row_means, grand_mean) = compute_kernel_means(&k)?;
+        let x = center_kernel_matrix(&k)?;
+        let (u_selection, sv_selection) = self.compute_top_svd(&x)?;
+        let signs = determine_signs(&u_selection);
+        // alpha scaled so that sqrt(lambda) * alpha reproduces the u_selection * sigma embeddings
+        let alpha = DMatrix::from_fn(u_selection.nrows(), self.embed_dim, |i, j| {
+            u_selection[(i, j)] / Float::sqrt(sv_selection[j])
+        });
+        // The centered kernel matrix is symmetric positive semi-definite, so its eigenvalues equal its singular values
+        // and the trace equals the sum of all of them, retained or not
+        let eigenvalues: Vec<T> = sv_selection.iter().cloned().collect();
+        let total_eigenvalue = x.trace();
+        let explained_variance_ratio = compute_explained_variance_ratio(&eigenvalues, total_eigenvalue)?;
+        Ok(FittedKernelPca {
+            kernel: self.kernel.clone(),
+            x_train: data.clone(),
+            alpha,
+            row_means,
+            grand_mean,
+            signs,
+            eigenvalues,
+            explained_variance_ratio
+        })
+    }
+
+    // Computes the top `embed_dim` left singular vectors/values of the (symmetric) centered
+    // kernel matrix, dispatching to the configured solver
+    fn compute_top_svd(&self, x: &DMatrix<T>) -> Result<(DMatrix<T>, DVector<T>), PcaError> {
+        let n = x.nrows();
+        match self.solver {
+            SvdSolver::Exact => exact_svd(x, self.embed_dim),
+            SvdSolver::Randomized { oversampling, power_iters } => {
+                let l = (self.embed_dim + oversampling).min(n);
+                // Clamped to n: no savings left over the exact solver, so just use it
+                if l >= n {
+                    exact_svd(x, self.embed_dim)
+                } else {
+                    randomized_svd(x, self.embed_dim, l, power_iters)
+                }
+            }
+        }
     }
 
     fn form_kernel_matrix(&self, x: &Vec<Vec<T>>) -> DMatrix<T> {
@@ -88,7 +223,63 @@ impl <T: Float + ComplexField + RealField> KernelPca<T> {
         return k;
     }
 
+    ///
+    /// Applies Kernel PCA to an already-computed symmetric Gram matrix, skipping kernel
+    /// evaluation entirely. This requires the kernel to be configured as `Kernel::Precomputed`,
+    /// and is useful when the similarity matrix comes from a non-vectorial source that this
+    /// crate's built-in kernels can't express
+    ///
+    /// # Arguments
+    ///
+    /// * `gram` - The precomputed, symmetric n x n Gram matrix
+    ///
+    pub fn apply_precomputed(&self, gram: &DMatrix<T>) -> Result<Vec<Vec<T>>, PcaError> {
+        self.validate_precomputed(gram)?;
+        let x = center_kernel_matrix(gram)?;
+        let (u_selection, sv_selection) = self.compute_top_svd(&x)?;
+        let signs = determine_signs(&u_selection);
+        let sigma = DMatrix::from_diagonal(&sv_selection.map(|v| Float::sqrt(v)));
+        let embeddings = &u_selection * sigma;
+        Ok(embeddings
+        .row_iter()
+        .map(|row| {
+            row.iter()
+            .enumerate()
+            .map(|(j, &val)| val * signs[j])
+            .collect()
+        })
+        .collect())
+    }
+
+    fn validate_precomputed(&self, gram: &DMatrix<T>) -> Result<(), PcaError> {
+        if !matches!(self.kernel, Kernel::Precomputed) {
+            return Err(PcaError::invalid_config("apply_precomputed requires a Precomputed kernel"));
+        }
+        let n = gram.nrows();
+        if n == 0 || gram.ncols() != n {
+            return Err(PcaError::invalid_data("Precomputed Gram matrix must be square and non-empty"));
+        }
+        let tolerance = T::from(1e-6).unwrap_or(T::epsilon());
+        for i in 0..n {
+            for j in 0..i {
+                if Float::abs(gram[(i, j)] - gram[(j, i)]) > tolerance {
+                    return Err(PcaError::invalid_data("Precomputed Gram matrix must be symmetric"));
+                }
+            }
+        }
+        if self.embed_dim == 0 {
+            return Err(PcaError::invalid_config("Embedding dimension must be positive"));
+        }
+        if self.embed_dim > n {
+            return Err(PcaError::invalid_config("Embedding dimension must be <= Gram matrix size"));
+        }
+        Ok(())
+    }
+
     fn validate(&self, data: &Vec<Vec<T>>) -> Result<(), PcaError> {
+        if matches!(self.kernel, Kernel::Precomputed) {
+            return Err(PcaError::invalid_config("Precomputed kernel requires apply_precomputed instead of fit/apply"));
+        }
         if data.len() == 0 {
             return Err(PcaError::invalid_data("Input data has no records"));
         }
@@ -107,10 +298,63 @@ impl <T: Float + ComplexField + RealField> KernelPca<T> {
         if self.embed_dim > dim {
             return Err(PcaError::invalid_config("Embeddind dimension must be <= data dimension"));
         }
+        if self.embed_dim > data.len() {
+            return Err(PcaError::invalid_config("Embedding dimension must be <= number of training points"));
+        }
         Ok(())
     }
 }
 
+impl <T: Float + ComplexField + RealField> FittedKernelPca<T> {
+
+    ///
+    /// Returns the retained eigenvalues of the centered training kernel matrix, in the
+    /// same descending order as the embedding dimensions
+    ///
+    pub fn eigenvalues(&self) -> &[T] {
+        &self.eigenvalues
+    }
+
+    ///
+    /// Returns the fraction of total variance captured by each retained dimension, computed
+    /// as each retained eigenvalue divided by the sum of all eigenvalues of the centered
+    /// training kernel matrix. Useful for choosing `embed_dim` without guesswork
+    ///
+    pub fn explained_variance_ratio(&self) -> &[T] {
+        &self.explained_variance_ratio
+    }
+
+    ///
+    /// Projects new, out-of-sample points into the embedding space learned by `fit`,
+    /// using the Nystrom formula to double-center each new kernel row against the
+    /// stored training statistics
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The out-of-sample data, as a vector of feature vectors
+    ///
+    pub fn transform(&self, data: &Vec<Vec<T>>) -> Result<Vec<Vec<T>>, PcaError> {
+        let n = self.x_train.len();
+        let tn = T::from(n).ok_or(PcaError::computation_failure("Unable to convert training size to float"))?;
+        Ok(data.iter().map(|point| {
+            let k_row: Vec<T> = self.x_train.iter().map(|x_i| self.kernel.compute(point, x_i)).collect();
+            let row_mean = k_row.iter().fold(T::zero(), |sum, &v| sum + v) / tn;
+            let k_centered: Vec<T> = k_row
+            .iter()
+            .zip(self.row_means.iter())
+            .map(|(&k_i, &rm_i)| k_i - rm_i - row_mean + self.grand_mean)
+            .collect();
+            (0..self.alpha.ncols()).map(|l| {
+                let coord = k_centered
+                .iter()
+                .enumerate()
+                .fold(T::zero(), |sum, (i, &kc_i)| sum + kc_i * self.alpha[(i, l)]);
+                coord * self.signs[l]
+            }).collect()
+        }).collect())
+    }
+}
+
 // Center the kernel matrix for kernel PCA
 fn center_kernel_matrix<T: Float + Scalar + Field>(k: &DMatrix<T>) -> Result<DMatrix<T>, PcaError> {
     let dim = k.nrows();
@@ -120,6 +364,18 @@ fn center_kernel_matrix<T: Float + Scalar + Field>(k: &DMatrix<T>) -> Result<DMa
     return Ok((&r * k) * &r);
 }
 
+// Compute the per-row means and grand mean of the (uncentered) training kernel matrix
+fn compute_kernel_means<T: Float + Scalar>(k: &DMatrix<T>) -> Result<(Vec<T>, T), PcaError> {
+    let dim = k.nrows();
+    let tdim = T::from(dim).ok_or(PcaError::computation_failure("Unable to convert dimension to float"))?;
+    let row_means: Vec<T> = k
+    .row_iter()
+    .map(|row| row.iter().fold(T::zero(), |sum, &v| sum + v) / tdim)
+    .collect();
+    let grand_mean = row_means.iter().fold(T::zero(), |sum, &v| sum + v) / tdim;
+    Ok((row_means, grand_mean))
+}
+
 // Center the input data for standard PCA
 fn center_data<T: Float + Scalar + Field>(x: &Vec<Vec<T>>) -> Result<DMatrix<T>, PcaError> {
     let dim = x[0].len();
@@ -135,7 +391,7 @@ fn center_data<T: Float + Scalar + Field>(x: &Vec<Vec<T>>) -> Result<DMatrix<T>,
     }
     Ok(DMatrix::from_rows(&x.iter().map(|row| {
         RowDVector::from_iterator(
-            dim, 
+            dim,
             row
             .iter()
             .zip(means.iter())
@@ -144,8 +400,89 @@ fn center_data<T: Float + Scalar + Field>(x: &Vec<Vec<T>>) -> Result<DMatrix<T>,
     }).collect::<Vec<_>>()))
 }
 
-fn determine_signs<T: Float>(u: &DMatrix<T>, dim: usize) -> Vec<T> {
-    u.columns(0, dim).column_iter().map(|column| {
+// Compute the per-row means and grand mean of the (uncentered) linear kernel matrix directly
+// from the training data, without materializing the n x n matrix itself: row_means[i] is the
+// dot product of x_i with the data mean, and the grand mean is the data mean dotted with itself
+fn linear_kernel_means<T: Float>(x: &Vec<Vec<T>>) -> Result<(Vec<T>, T), PcaError> {
+    let dim = x[0].len();
+    let n = T::from(x.len()).ok_or(PcaError::computation_failure("Unable to convert data length to float"))?;
+    let mut mean = vec![T::zero(); dim];
+    for row in x {
+        for (j, &val) in row.iter().enumerate() {
+            mean[j] = mean[j] + val;
+        }
+    }
+    for v in mean.iter_mut() {
+        *v = *v / n;
+    }
+    let row_means: Vec<T> = x
+    .iter()
+    .map(|row| row.iter().zip(mean.iter()).fold(T::zero(), |sum, (&v, &m)| sum + v * m))
+    .collect();
+    let grand_mean = mean.iter().fold(T::zero(), |sum, &m| sum + m * m);
+    Ok((row_means, grand_mean))
+}
+
+// Divides each retained eigenvalue by the sum of all of the centered kernel matrix's
+// eigenvalues, guarding against a near-zero sum (e.g. from degenerate/duplicate training
+// points) that would otherwise silently produce NaN/Inf ratios
+fn compute_explained_variance_ratio<T: Float>(eigenvalues: &[T], total_eigenvalue: T) -> Result<Vec<T>, PcaError> {
+    if total_eigenvalue <= T::epsilon() {
+        return Err(PcaError::computation_failure(
+            "Sum of centered kernel matrix eigenvalues is too close to zero to compute explained variance"
+        ));
+    }
+    Ok(eigenvalues.iter().map(|&ev| ev / total_eigenvalue).collect())
+}
+
+// Computes the top k left singular vectors/values of a matrix via the full dense SVD
+fn exact_svd<T: Float + ComplexField + RealField>(x: &DMatrix<T>, k: usize) -> Result<(DMatrix<T>, DVector<T>), PcaError> {
+    let svd = x.clone().svd(true, false);
+    let u = svd.u.ok_or(PcaError::computation_failure("SVD Failure"))?;
+    let sv = svd.singular_values.rows(0, k).clone_owned();
+    Ok((u.columns(0, k).clone_owned(), sv))
+}
+
+// Approximates the top k left singular vectors/values of a symmetric matrix x via randomized
+// range finding: sketch x with a random n x l Gaussian matrix (optionally sharpened with power
+// iterations), orthonormalize the sketch via QR, then take the exact SVD of the much smaller
+// projection of x onto that basis
+fn randomized_svd<T: Float + ComplexField + RealField>(
+    x: &DMatrix<T>,
+    k: usize,
+    l: usize,
+    power_iters: usize
+) -> Result<(DMatrix<T>, DVector<T>), PcaError> {
+    let omega = random_gaussian_matrix::<T>(x.nrows(), l)?;
+    let mut y = x * &omega;
+    for _ in 0..power_iters {
+        // x is symmetric, so x^T == x
+        y = x * (x * &y);
+    }
+    let q = y.qr().q();
+    let b = q.transpose() * x;
+    let svd_b = b.svd(true, false);
+    let u_tilde = svd_b.u.ok_or(PcaError::computation_failure("SVD Failure"))?;
+    let u = q * u_tilde;
+    let sv = svd_b.singular_values.rows(0, k).clone_owned();
+    Ok((u.columns(0, k).clone_owned(), sv))
+}
+
+// Draws an n x m matrix of iid standard Gaussian entries
+fn random_gaussian_matrix<T: Float + Scalar>(n: usize, m: usize) -> Result<DMatrix<T>, PcaError> {
+    let mut rng = thread_rng();
+    let mut omega = DMatrix::zeros(n, m);
+    for i in 0..n {
+        for j in 0..m {
+            let sample: f64 = StandardNormal.sample(&mut rng);
+            omega[(i, j)] = T::from(sample).ok_or(PcaError::computation_failure("Unable to convert random sample to float"))?;
+        }
+    }
+    Ok(omega)
+}
+
+fn determine_signs<T: Float>(u: &DMatrix<T>) -> Vec<T> {
+    u.column_iter().map(|column| {
         let mut max_abs_elem = T::zero();
         let mut flip = false;
         for &val in column.iter() {